@@ -1,17 +1,45 @@
-use actix_web::{web::Data, App, HttpServer};
+use std::{env, sync::Arc};
+
+use actix_web::{
+	middleware::{Condition, Logger},
+	web::Data,
+	App, HttpServer,
+};
+use managers::FeatureManager;
+use tracing_subscriber::EnvFilter;
 
 mod managers;
 mod models;
 mod web;
 
-struct AppData {}
+struct AppData {
+	features: Arc<FeatureManager>,
+}
+
+/// Whether to log a summary (path, status, latency) for every completed
+/// request. Controlled by `ACCESS_LOG=1`, off by default so production
+/// deployments can stay quiet while the `RUST_LOG` env-filter still governs
+/// the verbosity of load/cache tracing.
+fn access_log_enabled() -> bool {
+	env::var("ACCESS_LOG").is_ok_and(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+}
 
 /// Start the web server
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-	HttpServer::new(|| {
+	tracing_subscriber::fmt()
+		.with_env_filter(EnvFilter::from_default_env())
+		.init();
+
+	let features = FeatureManager::new();
+	let access_log = access_log_enabled();
+
+	HttpServer::new(move || {
 		App::new()
-			.app_data(Data::new(AppData {}))
+			.wrap(Condition::new(access_log, Logger::new("%r %s %Dms")))
+			.app_data(Data::new(AppData {
+				features: features.clone(),
+			}))
 			.service(web::module())
 	})
 	.bind(("127.0.0.1", 8080))?
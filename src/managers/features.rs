@@ -1,109 +1,186 @@
 use std::{
 	collections::HashSet,
 	future::Future,
-	ops::{Deref, DerefMut},
 	pin::Pin,
 	sync::Arc,
 	task::{Context, Poll, Waker},
+	time::Duration,
 };
 
 use dashmap::DashMap;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use parking_lot::Mutex;
 use tokio::{
-	fs::{read_dir, read_to_string},
 	spawn,
+	sync::mpsc::{self, UnboundedReceiver},
+	time::timeout,
 };
 
 use crate::models::Feature;
 
-enum LoadProgress {
-	Done,
-	Loading(HashSet<String>),
+use super::source::{FeatureSource, FsFeatureSource};
+
+/// How long to wait for the filesystem to go quiet before reconciling a
+/// batch of watcher events. Editors often emit a burst of writes/renames
+/// for a single save, so this coalesces them into one reconcile pass.
+const DEBOUNCE_WINDOW: Duration = Duration::from_millis(200);
+
+/// The state of a single feature in the cache
+#[derive(Clone)]
+enum LoadState {
+	Idle,
+	Loading,
+	Loaded(Feature),
+	Failed(String),
 }
 
-impl LoadProgress {
-	fn is_done(&self) -> bool {
-		matches!(self, Self::Done)
-	}
+/// Tracks the manager's overall load state.
+///
+/// `pending` holds every feature name touched by the batch (startup scan or
+/// watcher reconcile) that's still in progress — it's populated for the
+/// *whole* batch before the batch starts, so `is_done_loading` stays false
+/// for every file in the batch, not just the ones that haven't started yet.
+/// `in_flight` is a separate, per-call guard that stops two concurrent
+/// `load_feature` calls for the same name from racing each other.
+struct Progress {
+	pending: HashSet<String>,
+	in_flight: HashSet<String>,
+	bootstrapped: bool,
 }
 
-pub struct FeatureManager {
-	cache: DashMap<String, Feature>,
-	load_progress: Mutex<LoadProgress>,
+/// Commands sent to the background directory watcher
+enum WatcherCommand {
+	Start,
+	Pause,
+	Cancel,
+}
+
+pub struct FeatureManager<S = FsFeatureSource> {
+	source: S,
+	cache: DashMap<String, LoadState>,
+	progress: Mutex<Progress>,
 	done_wakers: Mutex<Vec<Waker>>,
 	feature_wakers: DashMap<String, Vec<Waker>>,
+	watcher_control: Mutex<Option<mpsc::UnboundedSender<WatcherCommand>>>,
 }
 
-pub struct FeatureListFuture {
-	feature_manager: Arc<FeatureManager>,
+pub struct FeatureListFuture<S> {
+	feature_manager: Arc<FeatureManager<S>>,
 }
 
-impl Future for FeatureListFuture {
+impl<S: FeatureSource> Future for FeatureListFuture<S> {
 	type Output = Box<[Feature]>;
 
 	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-		match self.feature_manager.load_progress.lock().deref() {
-			LoadProgress::Done => Poll::Ready(
+		if self.feature_manager.is_done_loading() {
+			Poll::Ready(
 				self.feature_manager
 					.cache
 					.iter()
-					.map(|kv| kv.value().clone())
+					.filter_map(|kv| match kv.value() {
+						LoadState::Loaded(feature) => Some(feature.clone()),
+						_ => None,
+					})
 					.collect(),
-			),
-			LoadProgress::Loading(_) => {
-				self.feature_manager
-					.done_wakers
-					.lock()
-					.push(cx.waker().clone());
-				Poll::Pending
-			}
+			)
+		} else {
+			self.feature_manager
+				.done_wakers
+				.lock()
+				.push(cx.waker().clone());
+			Poll::Pending
 		}
 	}
 }
 
-pub struct FeatureFuture {
-	feature_manager: Arc<FeatureManager>,
-	feature_name: String,
+pub struct FilteredFeatureListFuture<S, P> {
+	feature_manager: Arc<FeatureManager<S>>,
+	predicate: P,
 }
 
-impl Future for FeatureFuture {
-	type Output = Option<Feature>;
+impl<S: FeatureSource, P: Fn(&Feature) -> bool> Future for FilteredFeatureListFuture<S, P> {
+	type Output = Box<[Feature]>;
 
 	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
 		if self.feature_manager.is_done_loading() {
 			Poll::Ready(
 				self.feature_manager
 					.cache
-					.get(&self.feature_name)
-					.as_deref()
-					.cloned(),
+					.iter()
+					.filter_map(|kv| match kv.value() {
+						LoadState::Loaded(feature) if (self.predicate)(feature) => {
+							Some(feature.clone())
+						}
+						_ => None,
+					})
+					.collect(),
 			)
 		} else {
-			match self
-				.feature_manager
-				.feature_wakers
-				.get_mut(&self.feature_name)
-			{
-				Some(mut wakers) => wakers.push(cx.waker().clone()),
-				None => {
-					self.feature_manager
-						.feature_wakers
-						.insert(self.feature_name.clone(), vec![cx.waker().clone()]);
-				}
-			};
-
+			self.feature_manager
+				.done_wakers
+				.lock()
+				.push(cx.waker().clone());
 			Poll::Pending
 		}
 	}
 }
 
-impl FeatureManager {
-	pub fn new() -> Arc<Self> {
+pub struct FeatureFuture<S> {
+	feature_manager: Arc<FeatureManager<S>>,
+	feature_name: String,
+}
+
+impl<S: FeatureSource> Future for FeatureFuture<S> {
+	type Output = Option<Feature>;
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		let state = self
+			.feature_manager
+			.cache
+			.get(&self.feature_name)
+			.map(|kv| kv.value().clone());
+
+		match state {
+			Some(LoadState::Loaded(feature)) => return Poll::Ready(Some(feature)),
+			Some(LoadState::Failed(_)) => return Poll::Ready(None),
+			Some(LoadState::Idle) | Some(LoadState::Loading) => {}
+			None if self.feature_manager.is_done_loading() => return Poll::Ready(None),
+			None => {}
+		}
+
+		match self
+			.feature_manager
+			.feature_wakers
+			.get_mut(&self.feature_name)
+		{
+			Some(mut wakers) => wakers.push(cx.waker().clone()),
+			None => {
+				self.feature_manager
+					.feature_wakers
+					.insert(self.feature_name.clone(), vec![cx.waker().clone()]);
+			}
+		};
+
+		Poll::Pending
+	}
+}
+
+impl<S: FeatureSource> FeatureManager<S> {
+	/// Build a manager backed by an arbitrary `FeatureSource`. Unlike
+	/// `FeatureManager::new`, this does not start a filesystem watcher,
+	/// since not every source lives on disk.
+	pub fn with_source(source: S) -> Arc<Self> {
 		let this = Self {
+			source,
 			cache: DashMap::new(),
-			load_progress: Mutex::new(LoadProgress::Loading(HashSet::new())),
+			progress: Mutex::new(Progress {
+				pending: HashSet::new(),
+				in_flight: HashSet::new(),
+				bootstrapped: false,
+			}),
 			done_wakers: Mutex::new(Vec::new()),
 			feature_wakers: DashMap::new(),
+			watcher_control: Mutex::new(None),
 		};
 
 		let this = Arc::new(this);
@@ -116,84 +193,145 @@ impl FeatureManager {
 		this
 	}
 
+	#[tracing::instrument(level = "debug", skip(self))]
 	async fn load_all_features(&self) {
-		let mut files = read_dir("static/features").await.unwrap();
-		while let Some(entry) = files.next_entry().await.unwrap() {
-			if let Ok(metadata) = entry.metadata().await {
-				if metadata.is_file() {
-					self.load_feature(entry.file_name().to_string_lossy().deref())
-						.await;
-				} else {
-					eprintln!(
-						"Failed to load metadata for {}",
-						entry.file_name().to_string_lossy()
-					)
-				}
+		let feature_names = self.source.list().await;
+		tracing::debug!(count = feature_names.len(), "loading all features");
+
+		// mark the whole batch pending up front, so is_done_loading() stays
+		// false for every name in the batch until the last one lands, not
+		// just for whichever one is currently being awaited
+		self.progress
+			.lock()
+			.pending
+			.extend(feature_names.iter().cloned());
+
+		// record the names we know about but haven't started loading yet,
+		// so a query that lands mid-batch can tell "known, not loaded yet"
+		// apart from "never heard of this name"
+		for feature_name in &feature_names {
+			self.cache
+				.entry(feature_name.clone())
+				.or_insert(LoadState::Idle);
+		}
+
+		for feature_name in &feature_names {
+			self.load_feature(feature_name).await;
+		}
+
+		self.progress.lock().bootstrapped = true;
+		self.wake_if_done();
+
+		tracing::debug!(cached = self.cache.len(), "finished loading all features");
+	}
+
+	/// Force a full re-scan of the feature source: every name it lists is
+	/// reloaded and its cache entry refreshed in place. Entries for names
+	/// the source no longer lists are left in the cache.
+	pub async fn reload_all(&self) {
+		self.load_all_features().await;
+	}
+
+	#[tracing::instrument(level = "debug", skip(self))]
+	async fn load_feature(&self, feature_name: &str) {
+		// guard against two concurrent calls racing to load the same name;
+		// this is independent of `progress.pending`, which tracks batch
+		// completion rather than per-call de-duplication
+		{
+			let mut progress = self.progress.lock();
+			if progress.in_flight.contains(feature_name) {
+				return;
 			}
+			progress.in_flight.insert(feature_name.to_string());
 		}
 
-		dbg!(&self.cache);
+		self.cache
+			.insert(feature_name.to_string(), LoadState::Loading);
 
-		let mut load_progress = self.load_progress.lock();
-		*load_progress = LoadProgress::Done;
+		// load the feature
+		let state = match self.source.load(feature_name).await {
+			Ok(feature) => {
+				tracing::debug!("loaded feature");
+				LoadState::Loaded(feature)
+			}
+			Err(e) => {
+				tracing::warn!(error = %e, "failed to load feature");
+				LoadState::Failed(e.to_string())
+			}
+		};
+
+		self.cache.insert(feature_name.to_string(), state);
 
-		for waker in self.done_wakers.lock().iter() {
-			waker.clone().wake();
+		{
+			let mut progress = self.progress.lock();
+			progress.in_flight.remove(feature_name);
+			progress.pending.remove(feature_name);
 		}
 
-		for (_, wakers) in self.feature_wakers.clone() {
+		self.wake_feature(feature_name);
+		self.wake_if_done();
+	}
+
+	/// Remove a feature that no longer exists in the source and wake anyone
+	/// waiting on it.
+	fn forget_feature(&self, feature_name: &str) {
+		self.cache.remove(feature_name);
+		self.wake_feature(feature_name);
+	}
+
+	/// Wake every future waiting on a single feature's state
+	fn wake_feature(&self, feature_name: &str) {
+		if let Some((_, wakers)) = self.feature_wakers.remove(feature_name) {
 			for waker in wakers {
 				waker.wake();
 			}
 		}
 	}
 
-	async fn load_feature(&self, feature_name: &str) {
-		// check to see if the feature is already being loaded
-		{
-			let mut load_progress = self.load_progress.lock();
-			match load_progress.deref_mut() {
-				LoadProgress::Done => return,
-				LoadProgress::Loading(ref mut features) => {
-					if features.contains(feature_name) {
-						return;
-					} else {
-						features.insert(feature_name.to_string());
-					}
-				}
+	/// Wake every future waiting on the full feature list, if loading has
+	/// actually finished
+	fn wake_if_done(&self) {
+		if self.is_done_loading() {
+			for waker in self.done_wakers.lock().drain(..) {
+				waker.wake();
 			}
 		}
+	}
 
-		// load the feature
-		let json = read_to_string(format!("static/features/{}", feature_name))
-			.await
-			.unwrap();
-		let feature = serde_json::from_str::<Feature>(&json);
-
-		// log any failed parsing
-		if let Err(e) = feature {
-			eprintln!("{}", e);
+	/// Apply a debounced batch of watcher events: reload everything that
+	/// changed, drop everything that was deleted, and wake anyone waiting
+	/// on the result.
+	async fn reconcile(&self, changed: HashSet<String>, removed: HashSet<String>) {
+		if changed.is_empty() && removed.is_empty() {
 			return;
 		}
 
-		// cache the result
-		let feature = feature.unwrap();
-		self.cache.insert(feature_name.to_string(), feature);
+		tracing::debug!(
+			changed = changed.len(),
+			removed = removed.len(),
+			"reconciling feature cache after a watcher batch"
+		);
 
-		// update the loading progress
-		{
-			let mut load_progress = self.load_progress.lock();
-			if let LoadProgress::Loading(ref mut features) = load_progress.deref_mut() {
-				features.remove(feature_name);
-			}
+		// mark the whole batch pending up front (see load_all_features) so
+		// the list stays "loading" for the entire batch, not just between
+		// the moment a file starts loading and the moment it finishes
+		self.progress.lock().pending.extend(changed.iter().cloned());
+
+		for feature_name in &changed {
+			self.cache
+				.entry(feature_name.clone())
+				.or_insert(LoadState::Idle);
 		}
 
-		// wake the wakers
-		if let Some(wakers) = self.feature_wakers.get(feature_name) {
-			for waker in wakers.value() {
-				waker.clone().wake()
-			}
+		for feature_name in &removed {
+			self.forget_feature(feature_name);
+		}
+
+		for feature_name in &changed {
+			self.load_feature(feature_name).await;
 		}
+
+		self.wake_if_done();
 	}
 
 	pub async fn all_features(self: Arc<Self>) -> Box<[Feature]> {
@@ -203,8 +341,22 @@ impl FeatureManager {
 		.await
 	}
 
+	/// Every loaded feature matching `predicate`, applied while iterating
+	/// the cache rather than cloning the whole set first.
+	pub async fn all_features_filtered(
+		self: Arc<Self>,
+		predicate: impl Fn(&Feature) -> bool,
+	) -> Box<[Feature]> {
+		FilteredFeatureListFuture {
+			feature_manager: self.clone(),
+			predicate,
+		}
+		.await
+	}
+
 	pub fn is_done_loading(&self) -> bool {
-		self.load_progress.lock().deref().is_done()
+		let progress = self.progress.lock();
+		progress.bootstrapped && progress.pending.is_empty()
 	}
 
 	pub async fn get_feature(self: Arc<Self>, feature_name: &str) -> Option<Feature> {
@@ -214,4 +366,257 @@ impl FeatureManager {
 		}
 		.await
 	}
+
+	/// The features whose file failed to load, and why, so a diagnostics
+	/// panel can surface them instead of silently omitting them.
+	pub fn failed_features(&self) -> Vec<(String, String)> {
+		self.cache
+			.iter()
+			.filter_map(|kv| match kv.value() {
+				LoadState::Failed(error) => Some((kv.key().clone(), error.clone())),
+				_ => None,
+			})
+			.collect()
+	}
+
+	/// Pause the background directory watcher without cancelling it. A
+	/// no-op for managers with no watcher running.
+	pub fn pause_watcher(&self) {
+		if let Some(tx) = self.watcher_control.lock().as_ref() {
+			let _ = tx.send(WatcherCommand::Pause);
+		}
+	}
+
+	/// Resume a paused background directory watcher. A no-op for managers
+	/// with no watcher running.
+	pub fn start_watcher(&self) {
+		if let Some(tx) = self.watcher_control.lock().as_ref() {
+			let _ = tx.send(WatcherCommand::Start);
+		}
+	}
+
+	/// Stop the background directory watcher for good. A no-op for
+	/// managers with no watcher running.
+	pub fn cancel_watcher(&self) {
+		if let Some(tx) = self.watcher_control.lock().as_ref() {
+			let _ = tx.send(WatcherCommand::Cancel);
+		}
+	}
+}
+
+impl FeatureManager<FsFeatureSource> {
+	/// Build a manager backed by `static/features` on disk, watching it in
+	/// the background for changes.
+	pub fn new() -> Arc<Self> {
+		let this = Self::with_source(FsFeatureSource::default());
+
+		let (watcher_control, watcher_commands) = mpsc::unbounded_channel();
+		*this.watcher_control.lock() = Some(watcher_control);
+
+		let clone = this.clone();
+		spawn(async move {
+			clone.watch_for_changes(watcher_commands).await;
+		});
+
+		this
+	}
+
+	/// Watch the source directory for changes and incrementally reconcile
+	/// the cache, debouncing bursts of filesystem events into single
+	/// batches.
+	async fn watch_for_changes(self: Arc<Self>, mut commands: UnboundedReceiver<WatcherCommand>) {
+		let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+
+		let mut watcher: RecommendedWatcher =
+			match notify::recommended_watcher(move |res: notify::Result<Event>| {
+				if let Ok(event) = res {
+					let _ = event_tx.send(event);
+				}
+			}) {
+				Ok(watcher) => watcher,
+				Err(e) => {
+					tracing::error!(error = %e, "failed to start feature watcher");
+					return;
+				}
+			};
+
+		if let Err(e) = watcher.watch(self.source.path(), RecursiveMode::NonRecursive) {
+			tracing::error!(error = %e, dir = %self.source.path().display(), "failed to watch feature directory");
+			return;
+		}
+
+		let mut paused = false;
+
+		loop {
+			tokio::select! {
+				command = commands.recv() => match command {
+					Some(WatcherCommand::Start) => paused = false,
+					Some(WatcherCommand::Pause) => paused = true,
+					Some(WatcherCommand::Cancel) | None => break,
+				},
+				event = event_rx.recv() => {
+					let Some(event) = event else { break };
+					if paused {
+						continue;
+					}
+
+					let mut changed = HashSet::new();
+					let mut removed = HashSet::new();
+					collect_event(&event, &mut changed, &mut removed);
+
+					// coalesce any further events until the directory goes quiet
+					while let Ok(Some(event)) = timeout(DEBOUNCE_WINDOW, event_rx.recv()).await {
+						collect_event(&event, &mut changed, &mut removed);
+					}
+
+					self.reconcile(changed, removed).await;
+				}
+			}
+		}
+	}
+}
+
+/// Sort a single `notify` event's paths into the changed/removed batches by
+/// file name, ignoring event kinds we don't care about (access, etc). A name
+/// sits in only one of the two batches: the latest event for it decides
+/// which, so a later create/modify wins over an earlier delete of the same
+/// file and a later delete wins over an earlier create/modify.
+fn collect_event(event: &Event, changed: &mut HashSet<String>, removed: &mut HashSet<String>) {
+	let (target, other) = match event.kind {
+		EventKind::Remove(_) => (&mut *removed, &mut *changed),
+		EventKind::Create(_) | EventKind::Modify(_) => (&mut *changed, &mut *removed),
+		_ => return,
+	};
+
+	for path in &event.paths {
+		if let Some(name) = path.file_name().and_then(|name| name.to_str()) {
+			other.remove(name);
+			target.insert(name.to_string());
+		}
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use std::collections::HashMap;
+
+	use super::*;
+	use crate::managers::source::LoadError;
+	use crate::models::FeatureStatus;
+
+	/// A `FeatureSource` the tests can fully control: which features exist,
+	/// and how long each one takes to "load".
+	struct FakeSource {
+		features: HashMap<String, Feature>,
+		delays: HashMap<String, Duration>,
+	}
+
+	impl FakeSource {
+		fn new(features: Vec<Feature>) -> Self {
+			Self {
+				features: features
+					.into_iter()
+					.map(|feature| (feature.name.clone(), feature))
+					.collect(),
+				delays: HashMap::new(),
+			}
+		}
+
+		fn with_delay(mut self, name: &str, delay: Duration) -> Self {
+			self.delays.insert(name.to_string(), delay);
+			self
+		}
+	}
+
+	impl FeatureSource for FakeSource {
+		async fn list(&self) -> Vec<String> {
+			self.features.keys().cloned().collect()
+		}
+
+		async fn load(&self, name: &str) -> Result<Feature, LoadError> {
+			let delay = self.delays.get(name).copied().unwrap_or_default();
+			if !delay.is_zero() {
+				tokio::time::sleep(delay).await;
+			}
+
+			self.features
+				.get(name)
+				.cloned()
+				.ok_or_else(|| LoadError::Io(std::io::Error::from(std::io::ErrorKind::NotFound)))
+		}
+	}
+
+	fn feature(name: &str, status: FeatureStatus, version: Option<&str>, tags: &[&str]) -> Feature {
+		Feature {
+			name: name.to_string(),
+			description: String::new(),
+			tracking_issue: None,
+			status,
+			version: version.map(str::to_string),
+			tags: tags.iter().map(|tag| tag.to_string()).collect(),
+		}
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn reconcile_marks_the_whole_batch_pending_up_front() {
+		// "a" loads instantly, "b" takes 100ms: whichever one the reconcile
+		// loop happens to process first, the batch as a whole must still
+		// report itself as loading until *both* have landed.
+		let manager = FeatureManager::with_source(
+			FakeSource::new(Vec::new()).with_delay("b", Duration::from_millis(100)),
+		);
+
+		// let the (empty) initial scan finish
+		manager.clone().all_features().await;
+		assert!(manager.is_done_loading());
+
+		let changed = HashSet::from(["a".to_string(), "b".to_string()]);
+		let reconciling = manager.clone();
+		let handle = tokio::spawn(async move {
+			reconciling.reconcile(changed, HashSet::new()).await;
+		});
+
+		// give "a" (and the reconcile loop itself) time to finish, but not "b"
+		tokio::time::advance(Duration::from_millis(10)).await;
+		assert!(
+			!manager.is_done_loading(),
+			"a batch with an in-flight file must not report itself as done loading"
+		);
+
+		tokio::time::advance(Duration::from_millis(200)).await;
+		handle.await.unwrap();
+		assert!(manager.is_done_loading());
+	}
+
+	#[tokio::test]
+	async fn all_features_filtered_applies_the_predicate_during_iteration() {
+		let manager = FeatureManager::with_source(FakeSource::new(vec![
+			feature(
+				"async-closures",
+				FeatureStatus::Unstable,
+				None,
+				&["async"],
+			),
+			feature("let-chains", FeatureStatus::Stabilized, Some("1.88"), &[]),
+		]));
+
+		let all = manager.clone().all_features().await;
+		assert_eq!(all.len(), 2);
+
+		let stabilized = manager
+			.clone()
+			.all_features_filtered(|feature| feature.status == FeatureStatus::Stabilized)
+			.await;
+
+		assert_eq!(stabilized.len(), 1);
+		assert_eq!(stabilized[0].name, "let-chains");
+
+		let tagged_async = manager
+			.all_features_filtered(|feature| feature.tags.iter().any(|tag| tag == "async"))
+			.await;
+
+		assert_eq!(tagged_async.len(), 1);
+		assert_eq!(tagged_async[0].name, "async-closures");
+	}
 }
@@ -0,0 +1,4 @@
+mod features;
+mod source;
+
+pub use features::FeatureManager;
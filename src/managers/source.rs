@@ -0,0 +1,91 @@
+use std::{
+	future::Future,
+	io,
+	path::{Path, PathBuf},
+};
+
+use tokio::fs::{read_dir, read_to_string};
+
+use crate::models::Feature;
+
+const FEATURES_DIR: &str = "static/features";
+
+/// The error returned when a feature file can't be turned into a `Feature`
+#[derive(Debug)]
+pub enum LoadError {
+	Io(io::Error),
+	Parse(serde_json::Error),
+}
+
+impl std::fmt::Display for LoadError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Io(e) => write!(f, "failed to read feature file: {e}"),
+			Self::Parse(e) => write!(f, "failed to parse feature file: {e}"),
+		}
+	}
+}
+
+impl std::error::Error for LoadError {}
+
+/// A place `FeatureManager` can list and load features from. The filesystem
+/// (`FsFeatureSource`) is the default, but this lets a manager be backed by
+/// anything else instead — an embedded bundle, a remote HTTP directory, a
+/// database — without touching the caching/waker machinery.
+pub trait FeatureSource: Send + Sync + 'static {
+	/// List the names of every available feature
+	fn list(&self) -> impl Future<Output = Vec<String>> + Send;
+
+	/// Load a single feature by name
+	fn load(&self, name: &str) -> impl Future<Output = Result<Feature, LoadError>> + Send;
+}
+
+/// Reads features as JSON files from a directory on disk
+pub struct FsFeatureSource {
+	dir: PathBuf,
+}
+
+impl FsFeatureSource {
+	pub fn new(dir: impl Into<PathBuf>) -> Self {
+		Self { dir: dir.into() }
+	}
+
+	pub(crate) fn path(&self) -> &Path {
+		&self.dir
+	}
+}
+
+impl Default for FsFeatureSource {
+	fn default() -> Self {
+		Self::new(FEATURES_DIR)
+	}
+}
+
+impl FeatureSource for FsFeatureSource {
+	async fn list(&self) -> Vec<String> {
+		let mut files = read_dir(&self.dir).await.unwrap();
+		let mut names = Vec::new();
+
+		while let Some(entry) = files.next_entry().await.unwrap() {
+			match entry.metadata().await {
+				Ok(metadata) if metadata.is_file() => {
+					names.push(entry.file_name().to_string_lossy().into_owned());
+				}
+				_ => tracing::warn!(
+					file = %entry.file_name().to_string_lossy(),
+					"skipping feature entry that is not a file or whose metadata could not be read"
+				),
+			}
+		}
+
+		names
+	}
+
+	async fn load(&self, name: &str) -> Result<Feature, LoadError> {
+		let json = read_to_string(self.dir.join(name))
+			.await
+			.map_err(LoadError::Io)?;
+
+		serde_json::from_str(&json).map_err(LoadError::Parse)
+	}
+}
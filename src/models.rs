@@ -0,0 +1,46 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// The stabilization status of a nightly feature
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeatureStatus {
+	Unstable,
+	Stabilized,
+	Removed,
+}
+
+impl fmt::Display for FeatureStatus {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Unstable => write!(f, "unstable"),
+			Self::Stabilized => write!(f, "stabilized"),
+			Self::Removed => write!(f, "removed"),
+		}
+	}
+}
+
+/// A single nightly Rust feature, as loaded from `static/features`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Feature {
+	pub name: String,
+	pub description: String,
+	pub tracking_issue: Option<String>,
+	pub status: FeatureStatus,
+	pub version: Option<String>,
+	#[serde(default)]
+	pub tags: Vec<String>,
+}
+
+/// Parse a dotted version string like `"1.75"` into its numeric components,
+/// so versions compare/sort numerically (`1.10 > 1.5`) instead of as plain
+/// strings (where `"1.10" < "1.5"`). Unparseable components are treated as
+/// `0` rather than rejected, since feature files aren't expected to ship
+/// anything but plain `major.minor[.patch]` versions.
+pub fn parse_version(version: &str) -> Vec<u64> {
+	version
+		.split('.')
+		.map(|part| part.parse().unwrap_or(0))
+		.collect()
+}
@@ -1,11 +1,164 @@
-use actix_web::{get, web, Responder, Scope};
+use actix_web::{get, web, HttpResponse, Responder, Scope};
+use rss::{ChannelBuilder, ItemBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	models::{parse_version, FeatureStatus},
+	AppData,
+};
 
 #[get("/")]
 async fn index() -> impl Responder {
 	"Hello, world!"
 }
 
+#[derive(Serialize)]
+struct FeatureLoadError {
+	name: String,
+	error: String,
+}
+
+/// Reports which feature files failed to parse, and why, so a front-end
+/// diagnostics panel can surface them instead of features just silently
+/// vanishing from the list.
+#[get("/diagnostics/features")]
+async fn feature_diagnostics(data: web::Data<AppData>) -> impl Responder {
+	let errors: Vec<FeatureLoadError> = data
+		.features
+		.failed_features()
+		.into_iter()
+		.map(|(name, error)| FeatureLoadError { name, error })
+		.collect();
+
+	web::Json(errors)
+}
+
+/// Serves an RSS feed of every loaded feature, one `<item>` per feature, so
+/// people can subscribe to follow new and newly-stabilized nightly features.
+#[get("/feed.xml")]
+async fn feed(data: web::Data<AppData>) -> impl Responder {
+	let mut features = data.features.clone().all_features().await.into_vec();
+	features.sort_by(|a, b| {
+		let a_version = a.version.as_deref().map(parse_version);
+		let b_version = b.version.as_deref().map(parse_version);
+		a_version.cmp(&b_version).then_with(|| a.name.cmp(&b.name))
+	});
+
+	let items = features
+		.iter()
+		.map(|feature| {
+			let mut description = format!("{}\n\nStatus: {}", feature.description, feature.status);
+			if let Some(version) = &feature.version {
+				description.push_str(&format!(" (since {version})"));
+			}
+
+			ItemBuilder::default()
+				.title(Some(feature.name.clone()))
+				.description(Some(description))
+				.link(feature.tracking_issue.clone())
+				.build()
+		})
+		.collect::<Vec<_>>();
+
+	let channel = ChannelBuilder::default()
+		.title("Nightly Rust Features")
+		.link("/")
+		.description("New and newly-stabilized nightly Rust features")
+		.items(items)
+		.build();
+
+	HttpResponse::Ok()
+		.content_type("application/rss+xml")
+		.body(channel.to_string())
+}
+
+/// A version query value, compared numerically (via [`parse_version`])
+/// rather than as a plain string. A bare value (`version=1.75`) matches
+/// exactly; prefixing it with `>=`, `<=`, `>`, or `<` compares instead, e.g.
+/// `version=>=1.75` for "stabilized at or after 1.75".
+enum VersionFilter {
+	Eq(Vec<u64>),
+	Ge(Vec<u64>),
+	Le(Vec<u64>),
+	Gt(Vec<u64>),
+	Lt(Vec<u64>),
+}
+
+impl VersionFilter {
+	fn parse(raw: &str) -> Self {
+		if let Some(rest) = raw.strip_prefix(">=") {
+			Self::Ge(parse_version(rest))
+		} else if let Some(rest) = raw.strip_prefix("<=") {
+			Self::Le(parse_version(rest))
+		} else if let Some(rest) = raw.strip_prefix('>') {
+			Self::Gt(parse_version(rest))
+		} else if let Some(rest) = raw.strip_prefix('<') {
+			Self::Lt(parse_version(rest))
+		} else {
+			Self::Eq(parse_version(raw))
+		}
+	}
+
+	fn matches(&self, version: &str) -> bool {
+		let actual = parse_version(version);
+		match self {
+			Self::Eq(target) => actual == *target,
+			Self::Ge(target) => actual >= *target,
+			Self::Le(target) => actual <= *target,
+			Self::Gt(target) => actual > *target,
+			Self::Lt(target) => actual < *target,
+		}
+	}
+}
+
+/// A structured filter for `GET /features`, parsed from the query string so
+/// the predicate built from it is type-checked rather than string-matched.
+#[derive(Deserialize)]
+struct FeatureFilter {
+	status: Option<FeatureStatus>,
+	tag: Option<String>,
+	version: Option<String>,
+}
+
+/// Lists every loaded feature matching the given `status`/`tag`/`version`
+/// query parameters, e.g. `/features?status=stabilized&tag=async`.
+#[get("/features")]
+async fn list_features(
+	data: web::Data<AppData>,
+	filter: web::Query<FeatureFilter>,
+) -> impl Responder {
+	let FeatureFilter {
+		status,
+		tag,
+		version,
+	} = filter.into_inner();
+	let version = version.as_deref().map(VersionFilter::parse);
+
+	let features = data
+		.features
+		.clone()
+		.all_features_filtered(move |feature| {
+			status.is_none_or(|status| feature.status == status)
+				&& tag
+					.as_deref()
+					.is_none_or(|tag| feature.tags.iter().any(|t| t == tag))
+				&& version.as_ref().is_none_or(|version| {
+					feature
+						.version
+						.as_deref()
+						.is_some_and(|v| version.matches(v))
+				})
+		})
+		.await;
+
+	web::Json(features)
+}
+
 /// The front-end scope for the web app
 pub fn module() -> Scope {
-	web::scope("/").service(index)
+	web::scope("/")
+		.service(index)
+		.service(feature_diagnostics)
+		.service(feed)
+		.service(list_features)
 }